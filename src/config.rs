@@ -1,17 +1,304 @@
 use home::home_dir;
-use std::{ffi::OsStr, fs, io::Error};
+use std::{ffi::OsStr, fs, io::Error, path::PathBuf};
 
 use colored::*;
 use inquire::{
     validator::{StringValidator, Validation},
     Confirm, Editor, InquireError, Select, Text,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::git;
+use crate::transport;
+
+/// A single conventional-commit type offered in the `ask_commit` prompt.
+///
+/// `name` is what ends up in the subject line (`feat`, `fix`, …) while
+/// `description` is the right-hand blurb shown in the `Select` list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitType {
+    pub name: String,
+    pub description: String,
+}
+
+impl CommitType {
+    fn new(name: &str, description: &str) -> Self {
+        CommitType {
+            name: name.to_owned(),
+            description: description.to_owned(),
+        }
+    }
+
+    /// The `name    description` line rendered in the type picker.
+    fn label(&self) -> String {
+        format!("{:<11} {}", self.name, self.description)
+    }
+}
+
+/// Where the GitHub token is stored and read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenBackend {
+    /// Git's own config (`ghl.token`), which on most setups is backed by the
+    /// OS keychain through the configured credential helper.
+    GitConfig,
+    /// The legacy plaintext `~/.ghl/token` file.
+    File,
+}
+
+impl Default for TokenBackend {
+    fn default() -> Self {
+        TokenBackend::GitConfig
+    }
+}
+
+/// Which transport `confirm_pr` uses to submit the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransportKind {
+    /// Push the branch and open a pull request on GitHub.
+    Github,
+    /// Format the commit as a patch series and email it to a mailing list.
+    Email,
+}
+
+impl Default for TransportKind {
+    fn default() -> Self {
+        TransportKind::Github
+    }
+}
+
+/// Settings for the email transport, read from the `[email]` table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// `From:` address stamped on the patch series.
+    #[serde(default)]
+    pub from: String,
+    /// Recipient / mailing-list addresses the series is sent to.
+    #[serde(default)]
+    pub recipients: Vec<String>,
+    /// Command the formatted patches are piped to.
+    #[serde(default = "default_send_command")]
+    pub send_command: String,
+}
+
+fn default_send_command() -> String {
+    String::from("git send-email")
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        EmailConfig {
+            from: String::new(),
+            recipients: Vec::new(),
+            send_command: String::from("git send-email"),
+        }
+    }
+}
+
+/// Persistent user settings, read from `~/.ghl/config.toml` and optionally
+/// layered with a `.ghl.toml` committed at the repository root.
+///
+/// The struct is the merged, ready-to-use view; the on-disk files are parsed
+/// into [`SettingsFile`] (all fields optional) so the repo-local file can
+/// override individual keys without having to restate the whole config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    /// Editor command invoked for the pull request description.
+    pub editor: String,
+    /// Base branch new branches are compared against by default.
+    pub default_base_branch: String,
+    /// Pre-filled pull request description.
+    pub default_description: String,
+    /// Authoritative backend for reading and writing the GitHub token.
+    pub token_backend: TokenBackend,
+    /// Transport used to submit the pull request.
+    pub transport: TransportKind,
+    // Table-valued fields MUST stay last: `toml` serializes in declaration
+    // order and rejects a scalar emitted after a table (`ValueAfterTable`), so
+    // `commit_types` (`[[commit_types]]`) and `email` (`[email]`) come at the end.
+    /// Conventional-commit types offered in `ask_commit`.
+    pub commit_types: Vec<CommitType>,
+    /// Email-transport settings.
+    pub email: EmailConfig,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            editor: String::from("vim"),
+            default_base_branch: String::from("main"),
+            default_description: String::new(),
+            token_backend: TokenBackend::default(),
+            transport: TransportKind::default(),
+            commit_types: vec![
+                CommitType::new("feat", "A new feature"),
+                CommitType::new("fix", "A bug fix"),
+                CommitType::new("docs", "Documentation only changes"),
+                CommitType::new(
+                    "style",
+                    "Changes that do not affect the meaning of the code",
+                ),
+                CommitType::new(
+                    "refactor",
+                    "A code change that neither fixes a bug nor adds a feature",
+                ),
+                CommitType::new("perf", "A code change that improves performance"),
+                CommitType::new("test", "Adding missing tests or correcting existing tests"),
+                CommitType::new(
+                    "build",
+                    "Changes that affect the build system or external dependencies",
+                ),
+                CommitType::new("ci", "Changes to our CI configuration files and scripts"),
+                CommitType::new("chore", "Other changes that don't modify src or test files"),
+                CommitType::new("revert", "Reverts a previous commit"),
+            ],
+            email: EmailConfig::default(),
+        }
+    }
+}
+
+/// On-disk representation: every key is optional so a partial file (global or
+/// repo-local) can override only the fields it cares about.
+#[derive(Debug, Default, Deserialize)]
+struct SettingsFile {
+    editor: Option<String>,
+    default_base_branch: Option<String>,
+    default_description: Option<String>,
+    token_backend: Option<TokenBackend>,
+    transport: Option<TransportKind>,
+    commit_types: Option<Vec<CommitType>>,
+    email: Option<EmailConfig>,
+}
+
+impl SettingsFile {
+    fn load(path: &PathBuf) -> Self {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) => return SettingsFile::default(),
+        };
+        match toml::from_str(&content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                // Warn rather than silently discarding the whole file, which
+                // would reset every configured key back to its default.
+                eprintln!("{}: ignoring {} ({})", "warning".yellow(), path.display(), e);
+                SettingsFile::default()
+            }
+        }
+    }
+
+    /// Overlay the set keys of `self` onto `base`, repo-local winning.
+    fn apply(self, base: &mut Settings) {
+        if let Some(editor) = self.editor {
+            base.editor = editor;
+        }
+        if let Some(branch) = self.default_base_branch {
+            base.default_base_branch = branch;
+        }
+        if let Some(desc) = self.default_description {
+            base.default_description = desc;
+        }
+        if let Some(types) = self.commit_types {
+            base.commit_types = types;
+        }
+        if let Some(backend) = self.token_backend {
+            base.token_backend = backend;
+        }
+        if let Some(transport) = self.transport {
+            base.transport = transport;
+        }
+        if let Some(email) = self.email {
+            base.email = email;
+        }
+    }
+}
+
+impl Settings {
+    /// Build the merged settings: built-in defaults, overlaid by the global
+    /// `~/.ghl/config.toml`, overlaid by the repo-root `.ghl.toml`.
+    ///
+    /// On first run the legacy `token`/`desc.md` files are imported and the
+    /// global config is written out so subsequent runs are self-describing.
+    pub fn load() -> Settings {
+        let mut settings = Settings::default();
+
+        let config_path = Settings::config_path();
+        let first_run = !config_path.exists();
+        if first_run {
+            Settings::migrate_legacy(&mut settings);
+        }
+
+        SettingsFile::load(&config_path).apply(&mut settings);
+
+        // Persist the global config on first run so the migration runs exactly
+        // once and subsequent runs are self-describing. Repo-local overrides
+        // are applied afterwards so they never leak into the global file.
+        if first_run {
+            let _ = settings.save();
+        }
+
+        if let Some(local) = Settings::repo_config_path() {
+            SettingsFile::load(&local).apply(&mut settings);
+        }
+
+        settings
+    }
+
+    /// Persist the current settings to the global `~/.ghl/config.toml`.
+    pub fn save(&self) -> Result<(), Error> {
+        let (dir_path, _, _) = Config::get_paths();
+        if fs::read_dir(&dir_path).is_err() {
+            fs::create_dir(&dir_path)?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(Settings::config_path(), content)
+    }
+
+    /// Import the pre-existing `desc.md` and `token` files so users upgrading
+    /// from the flat layout keep their saved description and credential.
+    ///
+    /// The description lands in `default_description`; the token is copied into
+    /// whichever backend is configured (for the file backend it already lives
+    /// in the right place, so only the git-config backend needs a write).
+    fn migrate_legacy(settings: &mut Settings) {
+        let (_, token_path, default_desc_path) = Config::get_paths();
+
+        if let Ok(desc) = fs::read_to_string(&default_desc_path) {
+            if !desc.is_empty() {
+                settings.default_description = desc;
+            }
+        }
+
+        if settings.token_backend == TokenBackend::GitConfig {
+            if let Ok(token) = fs::read_to_string(&token_path) {
+                let token = token.trim();
+                if !token.is_empty() {
+                    let _ = Config::write_token_to_git_config(token);
+                }
+            }
+        }
+    }
+
+    fn config_path() -> PathBuf {
+        let (dir_path, _, _) = Config::get_paths();
+        PathBuf::from(dir_path).join("config.toml")
+    }
+
+    /// `.ghl.toml` at the root of the current repository, if we are in one.
+    fn repo_config_path() -> Option<PathBuf> {
+        let root = git::get_repo_root().ok()?;
+        let path = PathBuf::from(root).join(".ghl.toml");
+        path.exists().then_some(path)
+    }
+}
 
 pub struct Config {
     pub pr_name: String,
     pub branch: String,
+    pub base: String,
+    pub pr_body: String,
 }
 
 impl Config {
@@ -28,30 +315,62 @@ impl Config {
         };
         let token = token.trim();
 
-        let (dir_path, token_path, _) = Config::get_paths();
+        match Settings::load().token_backend {
+            TokenBackend::GitConfig => Config::write_token_to_git_config(token)?,
+            TokenBackend::File => Config::write_token_to_file(token)?,
+        }
 
+        Ok(true)
+    }
+
+    pub fn get_github_token() -> Result<String, Error> {
+        // Read the configured backend first. With the credential backend, fall
+        // back to the legacy file so users who never migrated keep working;
+        // with the file backend, fall back to the credential backend.
+        let from_git = || Config::read_token_from_git_config().ok().filter(|t| !t.is_empty());
+        match Settings::load().token_backend {
+            TokenBackend::GitConfig => match from_git() {
+                Some(token) => Ok(token),
+                None => Config::read_token_from_file(),
+            },
+            TokenBackend::File => Config::read_token_from_file().or_else(|e| from_git().ok_or(e)),
+        }
+    }
+
+    /// Store the token as the `ghl.token` entry of the global git config,
+    /// which is typically persisted through the OS keychain helper.
+    fn write_token_to_git_config(token: &str) -> Result<(), Error> {
+        let mut config = git2::Config::open_default().map_err(git2_err)?;
+        config.set_str("ghl.token", token).map_err(git2_err)
+    }
+
+    fn read_token_from_git_config() -> Result<String, Error> {
+        let config = git2::Config::open_default().map_err(git2_err)?;
+        config.get_string("ghl.token").map_err(git2_err)
+    }
+
+    fn write_token_to_file(token: &str) -> Result<(), Error> {
+        let (dir_path, token_path, _) = Config::get_paths();
         if fs::read_dir(&dir_path).is_err() {
             fs::create_dir(&dir_path)?;
         }
         if fs::read(&token_path).is_err() {
             fs::File::create(&token_path)?;
         }
-        fs::write(&token_path, token)?;
-
-        Ok(true)
+        fs::write(&token_path, token)
     }
 
-    pub fn get_github_token() -> Result<String, Error> {
+    fn read_token_from_file() -> Result<String, Error> {
         let (_, token_path, _) = Config::get_paths();
-        let token = fs::read_to_string(token_path)?;
-        Ok(token)
+        fs::read_to_string(token_path)
     }
 
     pub fn set_default_desc() -> Result<bool, InquireError> {
-        let actual = Config::get_default_desc().unwrap_or_default();
+        let mut settings = Settings::load();
+        let actual = settings.default_description.clone();
         let desc = Editor::new("Pull request description")
             .with_predefined_text(&actual)
-            .with_editor_command(OsStr::new("vim"))
+            .with_editor_command(OsStr::new(&settings.editor))
             .prompt_skippable()?;
         let desc = match desc {
             Some(desc) => {
@@ -67,51 +386,26 @@ impl Config {
             return Ok(false);
         }
 
-        let (dir_path, _, default_desc_path) = Config::get_paths();
-
-        match fs::read_dir(&dir_path) {
-            Ok(_) => {}
-            Err(_) => {
-                fs::create_dir(&dir_path)?;
-            }
-        };
-        match fs::read(&default_desc_path) {
-            Ok(_) => {}
-            Err(_) => {
-                fs::File::create(&default_desc_path)?;
-            }
-        }
-        fs::write(&default_desc_path, desc)?;
+        settings.default_description = desc;
+        settings.save()?;
 
         Ok(true)
     }
 
     pub fn get_default_desc() -> Result<String, Error> {
-        let (_, _, default_desc_path) = Config::get_paths();
-        let default_desc = fs::read_to_string(default_desc_path)?;
-        Ok(default_desc)
+        Ok(Settings::load().default_description)
     }
 
     pub fn ask_commit() -> Result<(String, String, String), InquireError> {
-        let type_options: Vec<&str> = vec![
-            "feat        A new feature",
-            "fix         A bug fix",
-            "docs        Documentation only changes",
-            "style       Changes that do not affect the meaning of the code",
-            "refactor    A code change that neither fixes a bug nor adds a feature",
-            "perf        A code change that improves performance",
-            "test        Adding missing tests or correcting existing tests",
-            "build       Changes that affect the build system or external dependencies",
-            "ci          Changes to our CI configuration files and scripts",
-            "chore       Other changes that don't modify src or test files",
-            "revert      Reverts a previous commit",
-        ];
+        let settings = Settings::load();
+        let type_options: Vec<String> =
+            settings.commit_types.iter().map(CommitType::label).collect();
 
         let _type = Select::new("Type:", type_options).prompt()?;
         let _type = _type.split_whitespace().collect::<Vec<&str>>()[0];
         let _type = String::from(_type);
 
-        let scope = Text::new("Scope (optional):").prompt_skippable()?;
+        let scope = Config::ask_scope()?;
 
         let name = Text::new("Name:")
             .with_validators(&[Box::new(get_not_empty_validator())])
@@ -132,14 +426,76 @@ impl Config {
         Ok((commit_name, _type, name))
     }
 
+    /// Let the user pick a base branch from the local branches.
+    ///
+    /// The configured `default_base_branch` is pre-selected (listed first)
+    /// since a PR base is almost always the trunk, not the newest feature
+    /// branch; the remaining branches follow ordered by tip commit recency.
+    /// Falls back to the default base when there are no local branches.
+    fn choose_base() -> Result<String, InquireError> {
+        let default_base = Settings::load().default_base_branch;
+        let branches = git::list_branches();
+        if branches.is_empty() {
+            return Ok(default_base);
+        }
+
+        // Default base first (pre-selected), then everything else by recency.
+        let mut ordered: Vec<(String, Option<i64>)> = Vec::new();
+        if let Some((name, ts)) = branches.iter().find(|(n, _)| *n == default_base) {
+            ordered.push((name.clone(), *ts));
+        } else {
+            ordered.push((default_base.clone(), None));
+        }
+        ordered.extend(
+            branches
+                .into_iter()
+                .filter(|(n, _)| *n != default_base),
+        );
+
+        let options: Vec<String> = ordered.iter().map(format_branch_option).collect();
+
+        let choice = Select::new("Base branch:", options).prompt()?;
+        Ok(choice
+            .split('\t')
+            .next()
+            .unwrap_or(&default_base)
+            .to_owned())
+    }
+
+    /// Prompt for the commit scope, suggesting the monorepo groups that the
+    /// currently changed paths belong to.
+    ///
+    /// A prefix trie is built from every tracked directory prefix; each changed
+    /// path is walked through it to find the longest matching group, and the
+    /// resulting directories are offered as a ranked `Select`. When nothing
+    /// matches we fall back to the original free-text prompt.
+    fn ask_scope() -> Result<Option<String>, InquireError> {
+        let suggestions = suggest_scopes();
+        if suggestions.is_empty() {
+            return Text::new("Scope (optional):").prompt_skippable();
+        }
+
+        let mut options = suggestions;
+        options.push(String::from("(other)"));
+        let choice = Select::new("Scope (optional):", options).prompt()?;
+        if choice == "(other)" {
+            return Text::new("Scope (optional):").prompt_skippable();
+        }
+        Ok(Some(choice))
+    }
+
     pub fn ask_init() -> Result<(String, String, String), InquireError> {
         let (commit_name, _type, name) = Config::ask_commit()?;
 
         let branch = &name.replace(' ', "-").replace('\'', "").to_lowercase();
         let branch = format!("{}/{}", _type, branch);
 
+        let base = Config::choose_base()?;
         let repo = git::get_current_repo()?;
-        let gh_compare_url = format!("https://github.com/{}/compare/{}?expand=1", repo, branch);
+        let gh_compare_url = format!(
+            "https://github.com/{}/compare/{}...{}?expand=1",
+            repo, base, branch
+        );
 
         println!(
             "\
@@ -176,23 +532,68 @@ This will:
         }
 
         let branch = format!("{}/{}", _type, &linear_branch);
+        let base = Config::choose_base()?;
+
+        let mut config = Config {
+            pr_name,
+            branch,
+            base,
+            pr_body: Settings::load().default_description,
+        };
+
+        // Prepend the scope line so reviewers see the change size up front.
+        if let Some(line) = config.diff_summary_line() {
+            config.pr_body = if config.pr_body.is_empty() {
+                line
+            } else {
+                format!("{}\n\n{}", line, config.pr_body)
+            };
+        }
 
-        Ok(Config { pr_name, branch })
+        Ok(config)
     }
 
     pub fn confirm_pr(&self) -> Result<bool, InquireError> {
-        println!(
-            "\
-This will:
-1. Create a branch called {}.
-2. Create an empty commit.
-3. Push to the remote repository.
-4. Create a pull request named {}.
-5. Assign you the pull request.",
-            self.branch.bright_cyan(),
-            self.pr_name.bright_cyan(),
-        );
-        Confirm::new("Confirm? (y/n)").prompt()
+        let transport = transport::for_settings(&Settings::load());
+        println!("{}", transport.summary(self));
+
+        if let Some(stat) = self.diff_stat() {
+            println!(
+                "Change size: {} files, {} {}",
+                stat.files.to_string().bright_cyan(),
+                format!("+{}", stat.added).green(),
+                format!("-{}", stat.deleted).red(),
+            );
+        }
+
+        let confirmed = Confirm::new("Confirm? (y/n)").prompt()?;
+        if confirmed {
+            transport.submit(self)?;
+        }
+        Ok(confirmed)
+    }
+
+    /// Diff size of the current `HEAD` commit against the chosen base;
+    /// uncommitted worktree changes are not counted.
+    ///
+    /// Measured against `HEAD` rather than `self.branch` because `confirm_pr`
+    /// runs before the target branch and its commit exist, so `self.branch` is
+    /// not yet a resolvable ref.
+    fn diff_stat(&self) -> Option<git::DiffStat> {
+        git::diff_shortstat(&self.base, "HEAD").ok()
+    }
+
+    /// A `> +X −Y across Z files` scope line to prepend to the PR description,
+    /// or `None` when the branch has no diff against the base.
+    pub fn diff_summary_line(&self) -> Option<String> {
+        let stat = self.diff_stat()?;
+        if stat.files == 0 {
+            return None;
+        }
+        Some(format!(
+            "> +{} \u{2212}{} across {} files",
+            stat.added, stat.deleted, stat.files
+        ))
     }
 
     fn get_paths() -> (String, String, String) {
@@ -205,9 +606,132 @@ This will:
     }
 }
 
+/// Render a branch as a `name\t(<date>)` option, with the tip commit date in
+/// `YYYY-MM-DD` form. The name before the tab is what `choose_base` parses back
+/// out of the selection.
+fn format_branch_option((name, ts): &(String, Option<i64>)) -> String {
+    match ts.and_then(|ts| chrono::DateTime::from_timestamp(ts, 0)) {
+        Some(dt) => format!("{}\t({})", name, dt.format("%Y-%m-%d")),
+        None => name.clone(),
+    }
+}
+
+/// Bridge `git2` errors into the `std::io::Error` surface the token helpers
+/// already return.
+fn git2_err(e: git2::Error) -> Error {
+    Error::new(std::io::ErrorKind::Other, e)
+}
+
+/// Rank the monorepo groups touched by the current change.
+///
+/// Builds a prefix trie of tracked directory prefixes, walks each changed path
+/// through it to find the longest matching group, and returns the group names
+/// ordered by how many changed files fall under them (most touched first).
+fn suggest_scopes() -> Vec<String> {
+    rank_scopes(git::tracked_dir_prefixes(), git::changed_files())
+}
+
+/// Pure ranking behind [`suggest_scopes`]: build a prefix trie from the tracked
+/// directory `prefixes`, walk each path in `changed` through it to find the
+/// longest matching group, and return the group names ordered by how many
+/// changed files fall under them (most touched first, first-seen order breaking
+/// ties).
+fn rank_scopes(prefixes: Vec<Vec<String>>, changed: Vec<String>) -> Vec<String> {
+    use trie_rs::TrieBuilder;
+
+    if prefixes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut builder = TrieBuilder::new();
+    for prefix in &prefixes {
+        builder.push(prefix.clone());
+    }
+    let trie = builder.build();
+
+    // Group name -> number of changed files under it, preserving first-seen
+    // order so equally-touched groups stay stable.
+    let mut ranked: Vec<(String, usize)> = Vec::new();
+    for path in changed {
+        let components: Vec<String> = path.split('/').map(|c| c.to_string()).collect();
+        let longest: Option<Vec<String>> = trie
+            .common_prefix_search(&components)
+            .into_iter()
+            .max_by_key(|group: &Vec<String>| group.len());
+
+        if let Some(group) = longest {
+            let name = group.join("/");
+            match ranked.iter_mut().find(|(n, _)| *n == name) {
+                Some(entry) => entry.1 += 1,
+                None => ranked.push((name, 1)),
+            }
+        }
+    }
+
+    ranked.sort_by(|a, b| b.1.cmp(&a.1));
+    ranked.into_iter().map(|(name, _)| name).collect()
+}
+
 fn get_not_empty_validator() -> impl StringValidator {
     |value: &str| match value.is_empty() {
         true => Ok(Validation::Invalid("You must enter a value.".into())),
         false => Ok(Validation::Valid),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir(path: &str) -> Vec<String> {
+        path.split('/').map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn rank_scopes_longest_prefix_and_frequency() {
+        let prefixes = vec![dir("crates"), dir("crates/foo"), dir("crates/bar")];
+        let changed = vec![
+            String::from("crates/foo/a.rs"),
+            String::from("crates/foo/b.rs"),
+            String::from("crates/bar/c.rs"),
+            String::from("README.md"), // outside any group, ignored
+        ];
+
+        // foo touched twice, bar once; README has no matching group.
+        assert_eq!(
+            rank_scopes(prefixes, changed),
+            vec![String::from("crates/foo"), String::from("crates/bar")]
+        );
+    }
+
+    #[test]
+    fn rank_scopes_empty_prefixes() {
+        assert!(rank_scopes(Vec::new(), vec![String::from("a/b.rs")]).is_empty());
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        // Serialization must succeed: scalars precede the `[[commit_types]]` /
+        // `[email]` tables, so `toml` never hits `ValueAfterTable`.
+        let serialized = toml::to_string_pretty(&Settings::default())
+            .expect("default settings serialize to toml");
+        let parsed: Settings = toml::from_str(&serialized).expect("serialized settings re-parse");
+        assert_eq!(parsed.editor, "vim");
+        assert_eq!(parsed.transport, TransportKind::Github);
+        assert_eq!(parsed.commit_types.len(), 11);
+    }
+
+    #[test]
+    fn partial_email_table_keeps_transport() {
+        // A partial `[email]` table must not error out and discard the rest of
+        // the config (which would reset `transport` back to the default).
+        let file: SettingsFile =
+            toml::from_str("transport = \"email\"\n[email]\nrecipients = [\"dev@list\"]\n")
+                .expect("partial email table parses");
+        let mut settings = Settings::default();
+        file.apply(&mut settings);
+        assert_eq!(settings.transport, TransportKind::Email);
+        assert_eq!(settings.email.recipients, vec![String::from("dev@list")]);
+        assert_eq!(settings.email.send_command, "git send-email");
+    }
+}
@@ -0,0 +1,236 @@
+use std::process::Command;
+
+use inquire::InquireError;
+use regex::Regex;
+
+/// Parsed counts from a `git diff --shortstat` summary line.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiffStat {
+    pub files: u64,
+    pub added: u64,
+    pub deleted: u64,
+}
+
+/// Turn a failed `git` invocation into an `InquireError` so the prompt flow
+/// can bubble it up like any other error.
+fn io_err(message: &str) -> InquireError {
+    InquireError::IO(std::io::Error::new(std::io::ErrorKind::Other, message))
+}
+
+/// The `owner/name` slug of the current repository, derived from the
+/// `origin` remote URL.
+pub fn get_current_repo() -> Result<String, InquireError> {
+    let output = Command::new("git")
+        .args(["config", "--get", "remote.origin.url"])
+        .output()
+        .map_err(InquireError::IO)?;
+
+    if !output.status.success() {
+        return Err(io_err("could not read remote.origin.url"));
+    }
+
+    let url = String::from_utf8_lossy(&output.stdout);
+    let url = url.trim();
+    let slug = url
+        .strip_suffix(".git")
+        .unwrap_or(url)
+        .rsplit(|c| c == ':' || c == '/')
+        .take(2)
+        .collect::<Vec<&str>>();
+
+    match slug.as_slice() {
+        [name, owner] => Ok(format!("{}/{}", owner, name)),
+        _ => Err(io_err("could not parse repository from remote url")),
+    }
+}
+
+/// Summarise the size of the change between `base` and `branch` by parsing the
+/// single summary line of `git diff --shortstat <base>...<branch>`.
+///
+/// Each of the three counts defaults to zero when its token is absent (e.g. a
+/// diff with only insertions omits the deletions clause), matching the way
+/// Starship's `git_metrics` module reads the same line.
+pub fn diff_shortstat(base: &str, branch: &str) -> Result<DiffStat, InquireError> {
+    let output = Command::new("git")
+        .args(["diff", "--shortstat", &format!("{}...{}", base, branch)])
+        .output()
+        .map_err(InquireError::IO)?;
+
+    if !output.status.success() {
+        return Err(io_err("could not compute diff shortstat"));
+    }
+
+    let summary = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_shortstat(&summary))
+}
+
+fn parse_shortstat(summary: &str) -> DiffStat {
+    let count = |unit: &str| -> u64 {
+        Regex::new(&format!(r"(\d+) {}", unit))
+            .ok()
+            .and_then(|re| re.captures(summary))
+            .and_then(|caps| caps.get(1))
+            .and_then(|m| m.as_str().parse().ok())
+            .unwrap_or(0)
+    };
+
+    DiffStat {
+        files: count(r"files? changed"),
+        added: count(r"insertions?\(\+\)"),
+        deleted: count(r"deletions?\(-\)"),
+    }
+}
+
+/// Local branches paired with the Unix timestamp of each branch's tip commit,
+/// ordered most-recently-committed first.
+///
+/// Reads `git for-each-ref --sort=-committerdate refs/heads`; the timestamp is
+/// `None` for any ref git reports without a parseable committer date.
+pub fn list_branches() -> Vec<(String, Option<i64>)> {
+    let output = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--sort=-committerdate",
+            "refs/heads",
+            "--format=%(refname:short)%09%(committerdate:unix)",
+        ])
+        .output();
+
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_branch_line)
+        .collect()
+}
+
+/// Parse one `name\t<unix>` line from `for-each-ref`; `None` for a line with no
+/// tab (a blank line included), with the timestamp left `None` when the date
+/// token is missing or unparseable.
+fn parse_branch_line(line: &str) -> Option<(String, Option<i64>)> {
+    let (name, ts) = line.split_once('\t')?;
+    Some((name.to_owned(), ts.trim().parse::<i64>().ok()))
+}
+
+/// Paths changed in the working tree, both staged and unstaged, de-duplicated.
+pub fn changed_files() -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for args in [
+        ["diff", "--name-only"].as_slice(),
+        ["diff", "--name-only", "--cached"].as_slice(),
+    ] {
+        if let Ok(output) = Command::new("git").args(args).output() {
+            if output.status.success() {
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    let path = line.to_owned();
+                    if !path.is_empty() && !files.contains(&path) {
+                        files.push(path);
+                    }
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Every directory prefix of every tracked file, e.g. `crates/foo/src/lib.rs`
+/// yields `crates`, `crates/foo` and `crates/foo/src`. These are the candidate
+/// scope groups fed into the suggestion trie.
+pub fn tracked_dir_prefixes() -> Vec<Vec<String>> {
+    let output = match Command::new("git").args(["ls-files"]).output() {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    let mut prefixes: Vec<Vec<String>> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let components: Vec<&str> = line.split('/').collect();
+        // Drop the file name itself; only directory prefixes are groups.
+        for end in 1..components.len() {
+            let prefix: Vec<String> = components[..end].iter().map(|c| c.to_string()).collect();
+            if !prefixes.contains(&prefix) {
+                prefixes.push(prefix);
+            }
+        }
+    }
+    prefixes
+}
+
+/// Absolute path to the top-level directory of the current repository.
+pub fn get_repo_root() -> Result<String, InquireError> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .map_err(InquireError::IO)?;
+
+    if !output.status.success() {
+        return Err(io_err("not inside a git repository"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortstat_full_line() {
+        let stat = parse_shortstat(" 3 files changed, 12 insertions(+), 4 deletions(-)");
+        assert_eq!(
+            stat,
+            DiffStat {
+                files: 3,
+                added: 12,
+                deleted: 4
+            }
+        );
+    }
+
+    #[test]
+    fn shortstat_insertions_only() {
+        let stat = parse_shortstat(" 1 file changed, 7 insertions(+)");
+        assert_eq!(
+            stat,
+            DiffStat {
+                files: 1,
+                added: 7,
+                deleted: 0
+            }
+        );
+    }
+
+    #[test]
+    fn shortstat_deletions_only() {
+        let stat = parse_shortstat(" 2 files changed, 5 deletions(-)");
+        assert_eq!(
+            stat,
+            DiffStat {
+                files: 2,
+                added: 0,
+                deleted: 5
+            }
+        );
+    }
+
+    #[test]
+    fn shortstat_empty_input() {
+        assert_eq!(parse_shortstat(""), DiffStat::default());
+    }
+
+    #[test]
+    fn branch_line_with_and_without_timestamp() {
+        assert_eq!(
+            parse_branch_line("main\t1690000000"),
+            Some((String::from("main"), Some(1690000000)))
+        );
+        assert_eq!(
+            parse_branch_line("wip\t"),
+            Some((String::from("wip"), None))
+        );
+        assert_eq!(parse_branch_line("no-tab"), None);
+    }
+}
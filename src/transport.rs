@@ -0,0 +1,197 @@
+use std::process::{Command, Stdio};
+
+use colored::*;
+use inquire::InquireError;
+
+use crate::config::{Config, EmailConfig, Settings, TransportKind};
+
+/// A way of submitting a prepared branch as a reviewable change.
+///
+/// The GitHub path pushes the branch and opens a pull request; the email path
+/// formats the commit as a patch series and mails it to a list. `confirm_pr`
+/// selects one based on [`Settings::transport`] and prints its [`summary`].
+///
+/// [`summary`]: Transport::summary
+pub trait Transport {
+    /// Carry out the submission for the prepared `config`.
+    fn submit(&self, config: &Config) -> Result<(), InquireError>;
+
+    /// A human-readable description of what [`submit`] will do, shown on the
+    /// confirmation screen.
+    ///
+    /// [`submit`]: Transport::submit
+    fn summary(&self, config: &Config) -> String;
+}
+
+fn io_err(message: &str) -> InquireError {
+    InquireError::IO(std::io::Error::new(std::io::ErrorKind::Other, message))
+}
+
+/// Run `command` and turn a non-zero exit (or spawn failure) into an error.
+fn run_checked(command: &mut Command, failure: &str) -> Result<(), InquireError> {
+    let status = command.status().map_err(InquireError::IO)?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io_err(failure))
+    }
+}
+
+/// Pick the transport named by the merged configuration.
+pub fn for_settings(settings: &Settings) -> Box<dyn Transport> {
+    match settings.transport {
+        TransportKind::Github => Box::new(GithubTransport),
+        TransportKind::Email => Box::new(EmailTransport {
+            email: settings.email.clone(),
+        }),
+    }
+}
+
+/// The original flow: push the branch and open a pull request on GitHub.
+pub struct GithubTransport;
+
+impl Transport for GithubTransport {
+    fn submit(&self, _config: &Config) -> Result<(), InquireError> {
+        // The push / create-PR / self-assign steps are driven by the caller.
+        Ok(())
+    }
+
+    fn summary(&self, config: &Config) -> String {
+        format!(
+            "\
+This will:
+1. Create a branch called {}.
+2. Create an empty commit.
+3. Push to the remote repository.
+4. Create a pull request named {}.
+5. Assign you the pull request.",
+            config.branch.bright_cyan(),
+            config.pr_name.bright_cyan(),
+        )
+    }
+}
+
+/// Format the commit range as a patch series and pipe it to the configured
+/// mailer, in the spirit of `pushmail`.
+pub struct EmailTransport {
+    email: EmailConfig,
+}
+
+impl EmailTransport {
+    fn send_program(&self) -> (String, Vec<String>) {
+        let mut parts = self
+            .email
+            .send_command
+            .split_whitespace()
+            .map(|s| s.to_owned());
+        let program = parts.next().unwrap_or_else(|| String::from("sendmail"));
+        (program, parts.collect())
+    }
+
+    /// `true` when the configured mailer is `git send-email`, which takes patch
+    /// files as arguments rather than a series on stdin.
+    fn is_git_send_email(&self, program: &str, args: &[String]) -> bool {
+        program == "git" && args.first().map(String::as_str) == Some("send-email")
+    }
+
+    /// Send a pre-generated patch directory with `git send-email`, passing the
+    /// configured `from`/`recipients` as `--from`/`--to` flags.
+    fn send_with_git(&self, config: &Config, mut args: Vec<String>) -> Result<(), InquireError> {
+        let out_dir = std::env::temp_dir().join("ghl-patches");
+        let _ = std::fs::remove_dir_all(&out_dir);
+        std::fs::create_dir_all(&out_dir).map_err(InquireError::IO)?;
+
+        run_checked(
+            Command::new("git").args([
+                "format-patch",
+                &format!("{}..HEAD", config.base),
+                "-o",
+                &out_dir.to_string_lossy(),
+            ]),
+            "git format-patch failed",
+        )?;
+
+        if !self.email.from.is_empty() {
+            args.push(format!("--from={}", self.email.from));
+        }
+        for recipient in &self.email.recipients {
+            args.push(format!("--to={}", recipient));
+        }
+        args.push(String::from("--confirm=never"));
+        args.push(out_dir.to_string_lossy().into_owned());
+
+        run_checked(Command::new("git").args(&args), "git send-email failed")
+    }
+
+    /// Pipe the patch series to a stdin-consuming mailer (e.g. `sendmail`),
+    /// addressing it to the configured recipients and envelope sender.
+    fn send_with_stdin(
+        &self,
+        config: &Config,
+        program: &str,
+        mut args: Vec<String>,
+    ) -> Result<(), InquireError> {
+        let patches = Command::new("git")
+            .args([
+                "format-patch",
+                "--stdout",
+                &format!("{}..HEAD", config.base),
+            ])
+            .output()
+            .map_err(InquireError::IO)?;
+
+        if !patches.status.success() {
+            return Err(io_err("git format-patch failed"));
+        }
+
+        if !self.email.from.is_empty() {
+            args.push(String::from("-f"));
+            args.push(self.email.from.clone());
+        }
+        args.extend(self.email.recipients.iter().cloned());
+
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(InquireError::IO)?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            use std::io::Write;
+            stdin.write_all(&patches.stdout).map_err(InquireError::IO)?;
+        }
+
+        let status = child.wait().map_err(InquireError::IO)?;
+        if !status.success() {
+            return Err(io_err("mailer exited with a non-zero status"));
+        }
+        Ok(())
+    }
+}
+
+impl Transport for EmailTransport {
+    fn submit(&self, config: &Config) -> Result<(), InquireError> {
+        if self.email.recipients.is_empty() {
+            return Err(io_err("no email recipients configured"));
+        }
+
+        let (program, args) = self.send_program();
+        if self.is_git_send_email(&program, &args) {
+            self.send_with_git(config, args)
+        } else {
+            self.send_with_stdin(config, &program, args)
+        }
+    }
+
+    fn summary(&self, config: &Config) -> String {
+        format!(
+            "\
+This will:
+1. Format {}..HEAD as a patch series.
+2. Email it from {} to {}.",
+            config.base.bright_cyan(),
+            self.email.from.bright_cyan(),
+            self.email.recipients.join(", ").bright_cyan(),
+        )
+    }
+}